@@ -0,0 +1,209 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::Once;
+
+use crate::internal_prelude::*;
+
+/// A single frame of the native HDF5 error stack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorFrame {
+    pub major: String,
+    pub minor: String,
+    pub func: String,
+    pub file: String,
+    pub line: u32,
+    pub desc: String,
+    pub(crate) minor_code: H5E_minor_t,
+}
+
+impl ErrorFrame {
+    /// The frame's description. `Display`/`description()` on `Error` return the deepest frame's.
+    pub fn desc(&self) -> &str {
+        &self.desc
+    }
+}
+
+/// The full captured native error stack, in the order `H5Ewalk2(..., H5E_WALK_DOWNWARD, ...)`
+/// reports it: the outermost (API-entry) frame first, the deepest root-cause frame last.
+pub type ErrorStack = Vec<ErrorFrame>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    desc: String,
+    stack: ErrorStack,
+}
+
+impl Error {
+    pub(crate) fn new<S: Into<String>>(desc: S) -> Self {
+        Error { desc: desc.into(), stack: Vec::new() }
+    }
+
+    /// Builds an error whose `Display`/`description()` text is the deepest (root-cause) frame's
+    /// description.
+    pub(crate) fn from_stack(stack: ErrorStack) -> Self {
+        let desc =
+            stack.last().map(|frame| frame.desc.clone()).unwrap_or_else(|| "unknown HDF5 error".to_owned());
+        Error { desc, stack }
+    }
+
+    /// Returns the native HDF5 error stack captured when this error was raised, outermost frame
+    /// first, root cause last. Empty for errors that did not originate from a native HDF5 call.
+    pub fn stack(&self) -> &[ErrorFrame] {
+        &self.stack
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.desc)
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        &self.desc
+    }
+}
+
+impl From<String> for Error {
+    fn from(desc: String) -> Error {
+        Error::new(desc)
+    }
+}
+
+impl<'a> From<&'a str> for Error {
+    fn from(desc: &'a str) -> Error {
+        Error::new(desc.to_owned())
+    }
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// Disables the HDF5 library's default behavior of printing errors to stderr; we capture and
+/// surface the stack ourselves instead. Idempotent, called lazily from `h5check`, so it takes
+/// effect only after the first native call checked by `h5check` has returned; a failure of that
+/// first call is still printed by the library.
+fn silence_default_printing() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        crate::h5lock!(H5Eset_auto2(H5E_DEFAULT, None, ptr::null_mut()));
+    });
+}
+
+unsafe fn char_ptr_to_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+unsafe fn resolve_msg(msg_id: i64, msg_type: H5E_type_t) -> String {
+    let mut msg_type_out: H5E_type_t = msg_type;
+    let size = H5Eget_msg(msg_id, &mut msg_type_out, ptr::null_mut(), 0);
+    if size <= 0 {
+        return String::new();
+    }
+    let mut buf = vec![0 as c_char; size as usize + 1];
+    H5Eget_msg(msg_id, &mut msg_type_out, buf.as_mut_ptr(), buf.len());
+    char_ptr_to_string(buf.as_ptr())
+}
+
+extern "C" fn walk_cb(_n: c_uint, err_desc: *const H5E_error2_t, data: *mut c_void) -> herr_t {
+    unsafe {
+        let stack = &mut *(data as *mut ErrorStack);
+        let desc = &*err_desc;
+        let major = resolve_msg(desc.maj_num, H5E_MAJOR);
+        let minor = resolve_msg(desc.min_num, H5E_MINOR);
+        stack.push(ErrorFrame {
+            major,
+            minor,
+            func: char_ptr_to_string(desc.func_name),
+            file: char_ptr_to_string(desc.file_name),
+            line: desc.line,
+            desc: char_ptr_to_string(desc.desc),
+            minor_code: desc.min_num,
+        });
+    }
+    0
+}
+
+fn capture_stack() -> ErrorStack {
+    let mut stack: ErrorStack = Vec::new();
+    unsafe {
+        H5Ewalk2(
+            H5E_DEFAULT,
+            H5E_WALK_DOWNWARD,
+            Some(walk_cb),
+            &mut stack as *mut _ as *mut c_void,
+        );
+        H5Eclear2(H5E_DEFAULT);
+    }
+    stack
+}
+
+/// Converts the result of a native HDF5 call into a `Result`, capturing the library's error
+/// stack (see `ErrorStack`) when the call reports failure.
+pub fn h5check<T: H5ErrorCode>(value: T) -> Result<T> {
+    silence_default_printing();
+    if value.is_err() {
+        Err(Error::from_stack(capture_stack()))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Implemented for the native return types (`herr_t`, `hid_t`, ...) that signal failure via a
+/// negative value.
+pub trait H5ErrorCode: Copy {
+    fn is_err(self) -> bool;
+}
+
+impl H5ErrorCode for herr_t {
+    fn is_err(self) -> bool {
+        self < 0
+    }
+}
+
+impl H5ErrorCode for hid_t {
+    fn is_err(self) -> bool {
+        self < 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(desc: &str) -> ErrorFrame {
+        ErrorFrame {
+            major: "major".to_owned(),
+            minor: "minor".to_owned(),
+            func: "func".to_owned(),
+            file: "file.c".to_owned(),
+            line: 1,
+            desc: desc.to_owned(),
+            minor_code: 0,
+        }
+    }
+
+    #[test]
+    fn description_is_the_deepest_frame() {
+        let stack = vec![
+            frame("unable to synchronously write to dataset"),
+            frame("unable to open file: no such file or directory"),
+        ];
+        let err = Error::from_stack(stack);
+        assert_eq!(err.description(), "unable to open file: no such file or directory");
+        assert_eq!(err.stack()[0].desc, "unable to synchronously write to dataset");
+        assert_eq!(err.stack()[1].desc, "unable to open file: no such file or directory");
+    }
+
+    #[test]
+    fn description_falls_back_when_stack_is_empty() {
+        let err = Error::from_stack(Vec::new());
+        assert_eq!(err.description(), "unknown HDF5 error");
+    }
+}
@@ -84,7 +84,8 @@ macro_rules! assert_err_re {
     };
 }
 
-/// Run a potentially unsafe expression in a closure synchronized by a global reentrant mutex.
+/// Run a potentially unsafe expression in a closure synchronized by a global reentrant mutex
+/// (a no-op passthrough if the `threadsafe` feature is enabled; see `sync::sync`).
 #[macro_export]
 macro_rules! h5lock {
     ($expr:expr) => {{
@@ -123,6 +124,26 @@ pub(crate) trait H5Get: Copy + Default {
     fn h5get_d(func: Self::Func, id: hid_t) -> Self {
         Self::h5get(func, id).unwrap_or_else(|_| Self::default())
     }
+
+    /// Like `h5get`, but maps an error whose deepest frame has the HDF5 "not found" minor code
+    /// (`H5E_NOTFOUND`, see `is_not_set_err`) to `Ok(None)` instead of an `Err`, so callers can
+    /// tell a genuinely absent property from a failed query.
+    #[inline]
+    fn h5get_opt(func: Self::Func, id: hid_t) -> Result<Option<Self>> {
+        match Self::h5get(func, id) {
+            Ok(value) => Ok(Some(value)),
+            Err(ref err) if is_not_set_err(err) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Whether the error stack indicates the queried property was never set rather than a genuine
+/// failure (e.g. querying an optional filter/chunk parameter that wasn't applied). Only the
+/// deepest (root-cause) frame is consulted: `H5E_CANTGET` alone is too generic (it wraps
+/// unrelated genuine failures throughout the library), so it's deliberately not matched here.
+fn is_not_set_err(err: &crate::error::Error) -> bool {
+    err.stack().last().map_or(false, |frame| frame.minor_code == H5E_NOTFOUND)
 }
 
 macro_rules! h5get {
@@ -143,6 +164,17 @@ macro_rules! h5get_d {
     };
 }
 
+/// Like `h5get!`, but returns `Result<Option<...>>`: `Ok(None)` when the property was never set,
+/// `Err` on a genuine failure.
+macro_rules! h5get_opt {
+    ($func:ident($id:expr): $ty:ty) => {
+        <($ty,) as $crate::macros::H5Get>::h5get_opt($func as _, $id).map(|x| x.map(|v| v.0))
+    };
+    ($func:ident($id:expr): $($ty:ty),+) => {
+        <($($ty),+) as $crate::macros::H5Get>::h5get_opt($func as _, $id)
+    };
+}
+
 macro_rules! impl_h5get {
     ($($name:ident: $ty:ident),+) => {
         impl<$($ty),+> H5Get for ($($ty,)+)
@@ -164,3 +196,61 @@ impl_h5get!(a: A);
 impl_h5get!(a: A, b: B);
 impl_h5get!(a: A, b: B, c: C);
 impl_h5get!(a: A, b: B, c: C, d: D);
+impl_h5get!(a: A, b: B, c: C, d: D, e: E);
+impl_h5get!(a: A, b: B, c: C, d: D, e: E, f: F);
+impl_h5get!(a: A, b: B, c: C, d: D, e: E, f: F, g: G);
+impl_h5get!(a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, ErrorFrame};
+    use std::cell::RefCell;
+
+    fn frame(minor_code: H5E_minor_t, desc: &str) -> ErrorFrame {
+        ErrorFrame {
+            major: "major".to_owned(),
+            minor: "minor".to_owned(),
+            func: "func".to_owned(),
+            file: "file.c".to_owned(),
+            line: 1,
+            desc: desc.to_owned(),
+            minor_code,
+        }
+    }
+
+    #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+    struct Probe(u32);
+
+    thread_local! {
+        static NEXT: RefCell<Option<Result<Probe>>> = RefCell::new(None);
+    }
+
+    impl H5Get for Probe {
+        type Func = ();
+
+        fn h5get(_func: (), _id: hid_t) -> Result<Self> {
+            NEXT.with(|next| next.borrow_mut().take()).expect("test must set NEXT before calling")
+        }
+    }
+
+    #[test]
+    fn h5get_opt_maps_not_set_to_none() {
+        let err = Error::from_stack(vec![frame(H5E_NOTFOUND, "property not set")]);
+        NEXT.with(|next| *next.borrow_mut() = Some(Err(err)));
+        assert_eq!(Probe::h5get_opt((), 0).unwrap(), None);
+    }
+
+    #[test]
+    fn h5get_opt_propagates_genuine_failures() {
+        let err = Error::from_stack(vec![frame(H5E_CANTGET, "type mismatch")]);
+        NEXT.with(|next| *next.borrow_mut() = Some(Err(err)));
+        assert!(Probe::h5get_opt((), 0).is_err());
+    }
+
+    #[test]
+    fn h5get_opt_passes_through_success() {
+        NEXT.with(|next| *next.borrow_mut() = Some(Ok(Probe(42))));
+        assert_eq!(Probe::h5get_opt((), 0).unwrap(), Some(Probe(42)));
+    }
+}
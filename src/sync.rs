@@ -0,0 +1,45 @@
+use crate::internal_prelude::*;
+
+/// Runs `func`, synchronized across threads so that only one Rust thread is ever inside the
+/// (non-thread-safe, by default) HDF5 C library at a time.
+///
+/// With the `threadsafe` feature enabled, this becomes a passthrough: libhdf5's own internal
+/// locking provides the guarantee instead, and calls from multiple Rust threads can proceed
+/// concurrently. The first call checks `H5is_library_threadsafe` and panics if the linked libhdf5
+/// was not built with `--enable-threadsafe`.
+#[cfg(not(feature = "threadsafe"))]
+pub fn sync<T, F: FnOnce() -> T>(func: F) -> T {
+    use lazy_static::lazy_static;
+    use parking_lot::ReentrantMutex;
+
+    lazy_static! {
+        static ref LIBHDF5_MUTEX: ReentrantMutex<()> = ReentrantMutex::new(());
+    }
+
+    let _guard = LIBHDF5_MUTEX.lock();
+    func()
+}
+
+#[cfg(feature = "threadsafe")]
+pub fn sync<T, F: FnOnce() -> T>(func: F) -> T {
+    ensure_library_is_threadsafe();
+    func()
+}
+
+#[cfg(feature = "threadsafe")]
+fn ensure_library_is_threadsafe() {
+    use std::sync::Once;
+
+    static CHECK: Once = Once::new();
+    CHECK.call_once(|| unsafe {
+        let mut is_threadsafe: hbool_t = 0;
+        H5is_library_threadsafe(&mut is_threadsafe);
+        if is_threadsafe == 0 {
+            panic!(
+                "hdf5-rs was built with the `threadsafe` feature, but the linked libhdf5 was \
+                 not compiled with --enable-threadsafe; rebuild libhdf5 with that option or \
+                 disable the `threadsafe` feature"
+            );
+        }
+    });
+}